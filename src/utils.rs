@@ -1,7 +1,12 @@
+mod backend;
 mod small_primes;
 
 use rand_core::RngCore;
 use rug::{Assign, Complete, Integer};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub use backend::BigInt;
 
 /// Wraps any randomness source that implements [`rand_core::RngCore`] and makes
 /// it compatible with [`rug::rand`].
@@ -23,22 +28,20 @@ pub fn external_rand(rng: &mut impl RngCore) -> rug::rand::ThreadRandState {
 
 /// Checks that `x` is in Z*_n
 #[inline(always)]
-pub fn in_mult_group(x: &Integer, n: &Integer) -> bool {
-    x.cmp0().is_ge() && in_mult_group_abs(x, n)
+pub fn in_mult_group<B: BigInt>(x: &B, n: &B) -> bool {
+    !x.is_negative() && in_mult_group_abs(x, n)
 }
 
 /// Checks that `abs(x)` is in Z*_n
 #[inline(always)]
-pub fn in_mult_group_abs(x: &Integer, n: &Integer) -> bool {
-    x.gcd_ref(n).complete() == *Integer::ONE
+pub fn in_mult_group_abs<B: BigInt>(x: &B, n: &B) -> bool {
+    x.gcd(n) == B::one()
 }
 
 /// Samples `x` in Z*_n
-pub fn sample_in_mult_group(rng: &mut impl RngCore, n: &Integer) -> Integer {
-    let mut rng = external_rand(rng);
-    let mut x = Integer::new();
+pub fn sample_in_mult_group<B: BigInt>(rng: &mut impl RngCore, n: &B) -> B {
     loop {
-        x.assign(n.random_below_ref(&mut rng));
+        let x = B::random_below(rng, n);
         if in_mult_group(&x, n) {
             return x;
         }
@@ -58,95 +61,428 @@ pub fn generate_safe_prime(rng: &mut impl RngCore, bits: u32) -> Integer {
 /// [`generate_safe_prime`] is indistinguishable from optimal for 500-1700 bit
 /// lengths.
 pub fn sieve_generate_safe_primes(rng: &mut impl RngCore, bits: u32, amount: usize) -> Integer {
-    use rug::integer::IsPrime;
+    let small_primes = &small_primes::SMALL_PRIMES[0..amount.min(small_primes::SMALL_PRIMES.len())];
+    sieve_with_wheel(rng, bits, small_primes)
+}
+
+/// Generate a random safe prime, widening the trial-division wheel past the
+/// static [`small_primes::SMALL_PRIMES`] table using a [`PrimeSieve`].
+///
+/// `sieve` is grown (and cached) to hold at least `amount` primes; reusing
+/// the same `sieve` across several calls, e.g. when generating many large
+/// keys, amortizes that growth cost across all of them.
+pub fn generate_safe_prime_with(
+    rng: &mut impl RngCore,
+    bits: u32,
+    sieve: &mut PrimeSieve,
+    amount: usize,
+) -> Integer {
+    sieve_with_wheel(rng, bits, sieve.primes(amount))
+}
 
-    let amount = amount.min(small_primes::SMALL_PRIMES.len());
+/// Shared implementation of [`sieve_generate_safe_primes`] and
+/// [`generate_safe_prime_with`], parameterized over the small-prime wheel
+fn sieve_with_wheel(rng: &mut impl RngCore, bits: u32, small_primes: &[u32]) -> Integer {
     let mut rng = external_rand(rng);
-    let mut x = Integer::new();
 
-    'trial: loop {
-        // generate an odd number of length `bits - 2`
-        x.assign(Integer::random_bits(bits - 1, &mut rng));
+    loop {
+        // sample a fresh odd starting point of length `bits - 1`
+        let mut x = Integer::random_bits(bits - 1, &mut rng).complete();
         // `random_bits` is guaranteed to not set `bits-1`-th bit, but not
         // guaranteed to set the `bits-2`-th
         x.set_bit(bits - 2, true);
         x |= 1u32;
 
-        for &small_prime in &small_primes::SMALL_PRIMES[0..amount] {
-            let mod_result = x.mod_u(small_prime);
-            if mod_result == (small_prime - 1) / 2 {
-                continue 'trial;
+        // `residues[i] == x mod small_primes[i]`, kept up to date incrementally
+        // as `x` walks `x, x+2, x+4, ...` so most candidates are rejected with
+        // cheap `u32` arithmetic instead of a fresh big-integer `mod_u` each time
+        let mut residues: Vec<u32> = small_primes.iter().map(|&p| x.mod_u(p)).collect();
+
+        for _ in 0..SIEVE_WINDOW {
+            // `x` must stay a `bits-1`-bit number so that `2x+1` stays a
+            // `bits`-bit number; once the wheel walks it past that range,
+            // stop and draw a fresh starting point instead of overshooting
+            if x.significant_bits() >= bits {
+                break;
+            }
+
+            // `x` is rejected if it's divisible by a small prime, or if `2x+1`
+            // would be divisible by one (i.e. `x == (p-1)/2 (mod p)`)
+            let survives_wheel = small_primes
+                .iter()
+                .zip(&residues)
+                .all(|(&p, &r)| r != 0 && r != (p - 1) / 2);
+
+            if survives_wheel && baillie_psw(&x) {
+                let y = (&x << 1u32).complete() + 1;
+                if baillie_psw(&y) {
+                    #[cfg(feature = "zeroize")]
+                    zeroize_integer(&mut x);
+                    return y;
+                }
+            }
+
+            x += 2;
+            for (&p, r) in small_primes.iter().zip(&mut residues) {
+                *r += 2;
+                if *r >= p {
+                    *r -= p;
+                }
             }
         }
 
-        // 25 taken same as one used in mpz_nextprime
-        if let IsPrime::Yes | IsPrime::Probably = x.is_probably_prime(25) {
-            x <<= 1;
-            x += 1;
-            if let IsPrime::Yes | IsPrime::Probably = x.is_probably_prime(25) {
-                return x;
+        // exhausted the window around this starting point, draw a new one
+    }
+}
+
+/// Number of consecutive odd candidates sieved from a single random starting
+/// point before [`sieve_generate_safe_primes`] draws a fresh one.
+const SIEVE_WINDOW: usize = 1 << 15;
+
+/// A growable, cached list of small primes, for widening the trial-division
+/// wheel in [`generate_safe_prime_with`] past the ~2000 primes hardcoded in
+/// [`small_primes::SMALL_PRIMES`].
+///
+/// Primes are produced with a segmented Sieve of Eratosthenes: the sieved
+/// range is extended (and the new primes found in it cached) on demand, so a
+/// sieve reused across many [`generate_safe_prime_with`] calls only pays for
+/// each prime once.
+#[derive(Debug, Default, Clone)]
+pub struct PrimeSieve {
+    /// All primes found so far, in increasing order, starting at `2`
+    primes: Vec<u32>,
+    /// Every integer up to (and including) this bound has been sieved
+    sieved_up_to: u32,
+}
+
+impl PrimeSieve {
+    /// Creates an empty sieve; primes are generated lazily as needed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the first `count` odd primes (`2` is skipped, since every
+    /// candidate in [`sieve_with_wheel`]'s wheel is already odd), growing
+    /// the cache with additional sieve segments if it doesn't hold enough.
+    pub fn primes(&mut self, count: usize) -> &[u32] {
+        while self.primes.len() < count + 1 {
+            self.sieve_next_segment(count + 1);
+        }
+        &self.primes[1..=count]
+    }
+
+    /// Sieves however many more segments of consecutive integers are needed
+    /// to reach `at_least` cached primes, roughly doubling the sieved range
+    /// each time.
+    fn sieve_next_segment(&mut self, at_least: usize) {
+        if self.sieved_up_to == 0 {
+            // bootstrap: sieve a small prefix directly, so that every later
+            // (doubling) segment already has all the base primes (<= sqrt of
+            // its upper bound) it needs cached in `self.primes`
+            self.sieve_range(2, 64);
+        }
+        while self.primes.len() < at_least {
+            let low = self.sieved_up_to + 1;
+            let high = self.sieved_up_to.saturating_mul(2).max(self.sieved_up_to.saturating_add(64));
+            self.sieve_range(low, high);
+        }
+    }
+
+    /// Sieves `[low, high]` for primes using the base primes cached in
+    /// `self.primes`, appending any primes found to the cache.
+    fn sieve_range(&mut self, low: u32, high: u32) {
+        let mut is_composite = vec![false; (high - low + 1) as usize];
+
+        if low == 2 {
+            // no base primes cached yet: sieve this bootstrap segment against
+            // itself, in the usual Sieve-of-Eratosthenes way
+            let mut i = 2u32;
+            while i * i <= high {
+                if !is_composite[(i - low) as usize] {
+                    let mut m = i * i;
+                    while m <= high {
+                        is_composite[(m - low) as usize] = true;
+                        m += i;
+                    }
+                }
+                i += 1;
+            }
+        } else {
+            for &p in &self.primes {
+                let p = u64::from(p);
+                if p * p > u64::from(high) {
+                    break;
+                }
+                let mut m = u64::from(low).div_ceil(p) * p;
+                if m < p * p {
+                    m = p * p;
+                }
+                while m <= u64::from(high) {
+                    is_composite[(m - u64::from(low)) as usize] = true;
+                    m += p;
+                }
             }
         }
+
+        for (offset, &composite) in is_composite.iter().enumerate() {
+            if !composite {
+                self.primes.push(low + offset as u32);
+            }
+        }
+        self.sieved_up_to = high;
+    }
+}
+
+/// Overwrites the limbs backing `x` with zeros and resets it to `0`
+///
+/// `rug::Integer` doesn't implement [`Zeroize`] itself since GMP has no API
+/// for it, so this reaches into the raw `mpz_t` to wipe the heap allocation
+/// backing the limbs before GMP gets a chance to free (and potentially
+/// reuse without clearing) it.
+#[cfg(feature = "zeroize")]
+fn zeroize_integer(x: &mut Integer) {
+    // SAFETY: `d` points to `alloc` limbs owned by this `mpz_t`; we only
+    // overwrite bytes that GMP allocated for, and still owns through, `x`.
+    unsafe {
+        let raw = x.as_raw_mut();
+        let limb_size = core::mem::size_of::<gmp_mpfr_sys::gmp::limb_t>();
+        core::ptr::write_bytes((*raw).d.as_ptr().cast::<u8>(), 0, (*raw).alloc as usize * limb_size);
+    }
+    x.assign(Integer::ZERO);
+}
+
+/// Baillie-PSW probable prime test
+///
+/// Combines trial division by [`small_primes::SMALL_PRIMES`], a base-2 strong
+/// Miller-Rabin test, and a strong Lucas test with Selfridge parameters. No
+/// composite number is currently known to pass all three checks, which makes
+/// this a stronger guarantee than a fixed-round Miller-Rabin test alone.
+pub fn baillie_psw(n: &Integer) -> bool {
+    if n.cmp0().is_le() || *n == *Integer::ONE {
+        return false;
+    }
+    // `2` is accepted here directly: the base-2 Miller-Rabin test below
+    // would reject it (`2^1 mod 2 == 0`)
+    if *n == 2u32 {
+        return true;
+    }
+
+    for &small_prime in small_primes::SMALL_PRIMES.iter() {
+        let small_prime = Integer::from(small_prime);
+        if *n == small_prime {
+            return true;
+        }
+        if n.is_divisible(&small_prime) {
+            return false;
+        }
+    }
+
+    // `selfridge_d_q` below scans D = 5, -7, 9, ... for the first one with
+    // Jacobi(D, n) == -1; perfect squares have no such D (Jacobi is always 0
+    // or 1), so without this check a perfect square whose smallest factor
+    // exceeds the table above would make it loop forever
+    if n.is_perfect_square() {
+        return false;
+    }
+
+    is_strong_probable_prime_base2(n) && is_strong_lucas_probable_prime(n)
+}
+
+/// Strong Miller-Rabin probable prime test to base 2
+///
+/// Writes `n - 1 = d * 2^s` with `d` odd, then checks that `2^d == 1 (mod n)`
+/// or `2^(d * 2^r) == n - 1 (mod n)` for some `0 <= r < s`.
+fn is_strong_probable_prime_base2(n: &Integer) -> bool {
+    let n_minus_1 = (n - Integer::ONE).complete();
+    #[allow(clippy::expect_used)]
+    let s = n_minus_1.find_one(0).expect("n - 1 is even and non-zero");
+    let d = (&n_minus_1 >> s).complete();
+
+    #[allow(clippy::expect_used)]
+    let mut x: Integer = Integer::from(2u32)
+        .pow_mod_ref(&d, n)
+        .expect("d is non-negative")
+        .into();
+    if x == *Integer::ONE || x == n_minus_1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = (&x * &x).complete() % n;
+        if x == n_minus_1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Strong Lucas probable prime test with Selfridge parameters
+///
+/// Selects `D` as the first term of `5, -7, 9, -11, 13, ...` with Jacobi
+/// symbol `(D/n) == -1`, then sets `P = 1`, `Q = (1 - D) / 4` and checks
+/// the Lucas sequences `U_d, V_d (mod n)` where `n + 1 = d * 2^s`, `d` odd.
+fn is_strong_lucas_probable_prime(n: &Integer) -> bool {
+    let Some((d, q)) = selfridge_d_q(n) else {
+        // `gcd(|D|, n)` was a non-trivial factor of `n`
+        return false;
+    };
+
+    let n_plus_1 = (n + Integer::ONE).complete();
+    #[allow(clippy::expect_used)]
+    let s = n_plus_1.find_one(0).expect("n + 1 is even and non-zero");
+    let delta = (&n_plus_1 >> s).complete();
+
+    // modular inverse of `2`, which exists since `n` is odd: `(n + 1) / 2`
+    let inv_2 = (n_plus_1 >> 1u32).modulo(n);
+
+    // `u, v` hold `U_1, V_1` (`P` is fixed to `1`), `qk` holds `Q^1`
+    let mut u = Integer::from(1);
+    let mut v = Integer::from(1);
+    let mut qk = q.clone().modulo(n);
+
+    #[allow(clippy::expect_used)]
+    let bits = delta.significant_bits();
+    for i in (0..bits - 1).rev() {
+        // double: (U_k, V_k) -> (U_2k, V_2k)
+        u = (&u * &v).complete().modulo(n);
+        v = ((&v * &v).complete() - (Integer::from(2) * &qk)).modulo(n);
+        qk = (&qk * &qk).complete().modulo(n);
+
+        if delta.get_bit(i) {
+            // add one: (U_2k, V_2k) -> (U_2k+1, V_2k+1)
+            let d_u = (&d * &u).complete();
+            let new_u = ((&u + &v).complete() * &inv_2).modulo(n);
+            let new_v = ((d_u + &v).complete() * &inv_2).modulo(n);
+            u = new_u;
+            v = new_v;
+            qk = (&qk * &q).complete().modulo(n);
+        }
+    }
+
+    if u.cmp0().is_eq() || v.cmp0().is_eq() {
+        return true;
+    }
+    for _ in 1..s {
+        v = ((&v * &v).complete() - (Integer::from(2) * &qk)).modulo(n);
+        if v.cmp0().is_eq() {
+            return true;
+        }
+        qk = (&qk * &qk).complete().modulo(n);
+    }
+    false
+}
+
+/// Picks Selfridge's `D, Q` parameters for the strong Lucas test, scanning
+/// `D` over `5, -7, 9, -11, 13, ...`. Returns `None` if a non-trivial factor
+/// of `n` is found along the way, which means `n` is composite.
+fn selfridge_d_q(n: &Integer) -> Option<(Integer, Integer)> {
+    let mut d_abs = Integer::from(5);
+    let mut positive = true;
+    loop {
+        let d = if positive {
+            d_abs.clone()
+        } else {
+            (-&d_abs).complete()
+        };
+
+        let g = d_abs.gcd_ref(n).complete();
+        if g > *Integer::ONE && g < *n {
+            return None;
+        }
+
+        if d.jacobi(n) == -1 {
+            let q = (Integer::from(1) - &d).complete() / 4;
+            return Some((d, q));
+        }
+
+        d_abs += 2;
+        positive = !positive;
     }
 }
 
 /// Faster exponentiation `x^e mod N^2` when factorization of `N = pq` is known and `e` is fixed
-pub trait FactorizedExp: Sized {
+pub trait FactorizedExp<B: BigInt>: Sized {
     /// Precomputes data for exponentiation
-    fn build(e: &Integer, p: &Integer, q: &Integer) -> Option<Self>;
+    fn build(e: &B, p: &B, q: &B) -> Option<Self>;
     /// Returns `x^e mod (p q)^2`
-    fn exp(&self, x: &Integer) -> Integer;
+    fn exp(&self, x: &B) -> B;
 }
 
 /// Naive `x^e mod N` implementation without optimizations
 #[derive(Clone)]
-pub struct NaiveExp {
-    nn: Integer,
-    e: Integer,
+pub struct NaiveExp<B: BigInt> {
+    nn: B,
+    e: B,
 }
 
-impl FactorizedExp for NaiveExp {
-    fn build(e: &Integer, p: &Integer, q: &Integer) -> Option<Self> {
-        if e.cmp0().is_lt() || p.cmp0().is_le() || q.cmp0().is_le() {
+impl<B: BigInt> FactorizedExp<B> for NaiveExp<B> {
+    fn build(e: &B, p: &B, q: &B) -> Option<Self> {
+        if e.is_negative() || !p.is_positive() || !q.is_positive() {
             return None;
         }
-        let n = (p * q).complete();
+        // `n = p q` and `nn = n^2` each roughly double the bit length of
+        // their operands; widen `p`, `q` up front so `square` below has the
+        // precision to hold `nn` without wrapping (a no-op on
+        // arbitrary-precision backends, see [`BigInt::widened`])
+        let width = p.precision_bits().max(q.precision_bits()).saturating_mul(4);
+        let p = &p.widened(width);
+        let q = &q.widened(width);
+        let n = p.mul(q);
         Some(Self {
             e: e.clone(),
             nn: n.square(),
         })
     }
 
-    fn exp(&self, x: &Integer) -> Integer {
+    fn exp(&self, x: &B) -> B {
+        let x = x.widened(self.nn.precision_bits());
         // We check that `e` is non-negative at the construction in `Self::build`
         #[allow(clippy::expect_used)]
-        x.pow_mod_ref(&self.e, &self.nn)
+        x.pow_mod(&self.e, &self.nn)
             .expect("`e` is checked to be non-negative")
-            .into()
     }
 }
 
+/// Zeroes the secret exponent `e` on drop, leaving the public modulus `nn` intact
+#[cfg(all(feature = "zeroize", feature = "backend-rug"))]
+impl Zeroize for NaiveExp<Integer> {
+    fn zeroize(&mut self) {
+        zeroize_integer(&mut self.e);
+    }
+}
+
+#[cfg(all(feature = "zeroize", feature = "backend-rug"))]
+impl ZeroizeOnDrop for NaiveExp<Integer> {}
+
 /// Faster algorithm for exponentiation based on Chinese remainder theorem
 #[derive(Clone)]
-pub struct CrtExp {
-    pp: Integer,
-    qq: Integer,
-    e_mod_phi_pp: Integer,
-    e_mod_phi_qq: Integer,
-    beta: Integer,
+pub struct CrtExp<B: BigInt> {
+    pp: B,
+    qq: B,
+    e_mod_phi_pp: B,
+    e_mod_phi_qq: B,
+    beta: B,
 }
 
-impl FactorizedExp for CrtExp {
-    fn build(e: &Integer, p: &Integer, q: &Integer) -> Option<Self> {
-        if e.cmp0().is_lt() || p.cmp0().is_le() || q.cmp0().is_le() {
+impl<B: BigInt> FactorizedExp<B> for CrtExp<B> {
+    fn build(e: &B, p: &B, q: &B) -> Option<Self> {
+        if e.is_negative() || !p.is_positive() || !q.is_positive() {
             return None;
         }
 
-        let pp = p.square_ref().complete();
-        let qq = q.square_ref().complete();
-        let e_mod_phi_pp = e % (&pp - p).complete();
-        let e_mod_phi_qq = e % (&qq - q).complete();
-        let beta = pp.invert_ref(&qq)?.into();
+        // every intermediate below, up to the final `qq * pp` recombination
+        // in `exp`, stays within `8x` the larger of `p`/`q`'s starting
+        // precision; widen before squaring so fixed-precision backends don't
+        // wrap (a no-op on arbitrary-precision ones, see [`BigInt::widened`])
+        let width = p.precision_bits().max(q.precision_bits()).saturating_mul(8);
+        let p = &p.widened(width);
+        let q = &q.widened(width);
+        let e = &e.widened(width);
+
+        let pp = p.square();
+        let qq = q.square();
+        let e_mod_phi_pp = e.rem(&pp.sub(p));
+        let e_mod_phi_qq = e.rem(&qq.sub(q));
+        let beta = pp.invert(&qq)?;
         Some(Self {
             e_mod_phi_pp,
             e_mod_phi_qq,
@@ -156,9 +492,11 @@ impl FactorizedExp for CrtExp {
         })
     }
 
-    fn exp(&self, x: &Integer) -> Integer {
-        let s1 = (x % &self.pp).complete();
-        let s2 = (x % &self.qq).complete();
+    fn exp(&self, x: &B) -> B {
+        let width = self.pp.precision_bits().max(self.qq.precision_bits());
+        let x = &x.widened(width);
+        let s1 = x.rem(&self.pp);
+        let s2 = x.rem(&self.qq);
 
         // `e_mod_phi_pp` and `e_mod_phi_qq` are guaranteed to be non-negative by construction
         #[allow(clippy::expect_used)]
@@ -170,10 +508,25 @@ impl FactorizedExp for CrtExp {
             .pow_mod(&self.e_mod_phi_qq, &self.qq)
             .expect("exponent is guaranteed to be non-negative");
 
-        ((r2 - &r1) * &self.beta).modulo(&self.qq) * &self.pp + &r1
+        r2.sub_mod(&r1, &self.qq).mul(&self.beta).modulo(&self.qq).mul(&self.pp).add(&r1)
     }
 }
 
+/// Zeroes everything derived from the secret primes `p`, `q` on drop
+#[cfg(all(feature = "zeroize", feature = "backend-rug"))]
+impl Zeroize for CrtExp<Integer> {
+    fn zeroize(&mut self) {
+        zeroize_integer(&mut self.pp);
+        zeroize_integer(&mut self.qq);
+        zeroize_integer(&mut self.e_mod_phi_pp);
+        zeroize_integer(&mut self.e_mod_phi_qq);
+        zeroize_integer(&mut self.beta);
+    }
+}
+
+#[cfg(all(feature = "zeroize", feature = "backend-rug"))]
+impl ZeroizeOnDrop for CrtExp<Integer> {}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -186,4 +539,106 @@ mod test {
             assert_eq!(&prime, rug::Integer::ONE);
         }
     }
+
+    #[test]
+    fn baillie_psw_rejects_known_pseudoprimes() {
+        // Carmichael numbers: composite, yet pass a Fermat test for every
+        // base coprime to them
+        for n in [561u64, 1105, 1729, 41041] {
+            assert!(!super::baillie_psw(&rug::Integer::from(n)), "{n} is composite");
+        }
+        // known strong Lucas pseudoprimes with Selfridge parameters: these
+        // pass the Lucas half of BPSW on their own; here they're already
+        // rejected by trial division, as their smallest factors (53, 53, 73,
+        // 89) are in `SMALL_PRIMES`
+        for n in [5459u64, 5777, 10877, 16109] {
+            assert!(!super::baillie_psw(&rug::Integer::from(n)), "{n} is composite");
+        }
+    }
+
+    #[test]
+    fn baillie_psw_accepts_known_primes() {
+        for n in [2u64, 3, 5, 7, 11, 97, 7919, 99991, 104729, 1_299_709] {
+            assert!(super::baillie_psw(&rug::Integer::from(n)), "{n} is prime");
+        }
+    }
+
+    #[test]
+    fn prime_sieve_matches_small_primes_table() {
+        let small_primes = super::small_primes::SMALL_PRIMES;
+        let mut sieve = super::PrimeSieve::new();
+        assert_eq!(sieve.primes(small_primes.len()), small_primes);
+    }
+
+    #[test]
+    fn prime_sieve_grows_past_small_primes_table() {
+        let table_len = super::small_primes::SMALL_PRIMES.len();
+        let mut sieve = super::PrimeSieve::new();
+        let primes = sieve.primes(table_len + 50).to_vec();
+
+        assert_eq!(&primes[..table_len], super::small_primes::SMALL_PRIMES);
+        for window in primes.windows(2) {
+            assert!(window[0] < window[1], "{primes:?} isn't strictly increasing");
+        }
+        for &p in &primes[table_len..] {
+            assert!(super::baillie_psw(&rug::Integer::from(p)), "{p} is prime");
+        }
+    }
+
+    #[test]
+    fn generate_safe_prime_with_wheel_past_small_primes_table() {
+        let mut rng = rand_dev::DevRng::new();
+        let mut sieve = super::PrimeSieve::new();
+        let amount = super::small_primes::SMALL_PRIMES.len() + 50;
+
+        let mut prime = super::generate_safe_prime_with(&mut rng, 200, &mut sieve, amount);
+        prime >>= 199;
+        assert_eq!(&prime, rug::Integer::ONE);
+    }
+
+    /// `CrtExp` must compute the same thing no matter which [`super::BigInt`]
+    /// backend it's instantiated with.
+    #[cfg(feature = "backend-crypto-bigint")]
+    #[test]
+    fn crt_exp_matches_across_backends() {
+        use crypto_bigint::BoxedUint;
+
+        use super::{CrtExp, FactorizedExp};
+
+        // `p`, `q` at 64-bit precision each: `pp = p^2`/`qq = q^2` already
+        // need 128 bits, and the final `qq * pp` recombination in `exp` needs
+        // 256; `CrtExp::build`/`exp` must widen these themselves (neither
+        // side pre-widens here), otherwise the crypto-bigint backend would
+        // silently wrap and disagree with `rug`
+        let (p, q, e, x) = (
+            18_446_744_073_709_551_557u64,
+            18_446_744_073_709_551_533u64,
+            17u64,
+            123_456u64,
+        );
+
+        let rug_result = {
+            let (p, q, e, x) = (
+                rug::Integer::from(p),
+                rug::Integer::from(q),
+                rug::Integer::from(e),
+                rug::Integer::from(x),
+            );
+            let exp = CrtExp::build(&e, &p, &q).expect("p, q, e are valid");
+            exp.exp(&x).to_string_radix(10)
+        };
+
+        let crypto_bigint_result = {
+            let (p, q, e, x) = (
+                BoxedUint::from(p),
+                BoxedUint::from(q),
+                BoxedUint::from(e),
+                BoxedUint::from(x),
+            );
+            let exp = CrtExp::build(&e, &p, &q).expect("p, q, e are valid");
+            exp.exp(&x).to_string()
+        };
+
+        assert_eq!(rug_result, crypto_bigint_result);
+    }
 }
@@ -0,0 +1,258 @@
+use rand_core::RngCore;
+
+/// Big-integer operations needed to power [`crate::utils::FactorizedExp`],
+/// [`crate::utils::in_mult_group`] and [`crate::utils::sample_in_mult_group`],
+/// abstracted so the backend doing the actual arithmetic can be swapped out.
+///
+/// The default backend wraps `rug`/GMP and is implemented behind the
+/// `backend-rug` feature (enabled by default). An alternative, pure-Rust
+/// `crypto-bigint` backend is available behind `backend-crypto-bigint`, for
+/// `no_std`/WASM targets where linking GMP isn't an option.
+///
+/// Safe prime generation (`sieve_generate_safe_primes`, `baillie_psw` and
+/// friends) isn't generic over this trait yet: it leans on GMP-specific bit
+/// operations (`find_one`, `significant_bits`, `jacobi`, ...) that aren't
+/// worth generalizing until a second backend actually needs them.
+pub trait BigInt: Clone + PartialEq + Sized {
+    /// `0`
+    fn zero() -> Self;
+    /// `1`
+    fn one() -> Self;
+    /// Whether `self < 0`
+    fn is_negative(&self) -> bool;
+    /// Whether `self > 0`
+    fn is_positive(&self) -> bool;
+    /// `self + other`
+    fn add(&self, other: &Self) -> Self;
+    /// `self - other`
+    fn sub(&self, other: &Self) -> Self;
+    /// `(self - other) mod modulus`, given `self, other` both in `0..modulus`
+    fn sub_mod(&self, other: &Self, modulus: &Self) -> Self;
+    /// `self * other`
+    fn mul(&self, other: &Self) -> Self;
+    /// `self^2`
+    fn square(&self) -> Self;
+    /// Truncating remainder of `self / modulus`
+    fn rem(&self, modulus: &Self) -> Self;
+    /// `self mod modulus`, always in `0..modulus`
+    fn modulo(&self, modulus: &Self) -> Self;
+    /// `self^exponent mod modulus`, or `None` if `exponent` is negative
+    fn pow_mod(&self, exponent: &Self, modulus: &Self) -> Option<Self>;
+    /// The inverse of `self` modulo `modulus`, or `None` if it doesn't exist
+    fn invert(&self, modulus: &Self) -> Option<Self>;
+    /// `gcd(self, other)`
+    fn gcd(&self, other: &Self) -> Self;
+    /// Samples a uniformly random value in `0..bound`
+    fn random_below(rng: &mut impl RngCore, bound: &Self) -> Self;
+
+    /// The bit precision backing this value.
+    ///
+    /// For arbitrary-precision backends (e.g. `rug::Integer`) this is just
+    /// informational. For fixed-precision backends (e.g.
+    /// `crypto_bigint::BoxedUint`), it's the allocated width that [`Self::mul`]
+    /// and [`Self::square`] **don't grow past** — multiplying two values
+    /// whose product doesn't fit in `max(self, other)`'s precision silently
+    /// wraps `mod 2^precision_bits()` instead of erroring. Callers that chain
+    /// several multiplications (like [`crate::utils::CrtExp::build`]) must
+    /// [`Self::widened`] their operands up front to a width the whole chain
+    /// fits in.
+    fn precision_bits(&self) -> u32;
+    /// Returns a value equal to `self`, backed by at least `bits` of
+    /// precision. A no-op for arbitrary-precision backends; for
+    /// fixed-precision ones, never truncates (it's `bits.max(self.precision_bits())`).
+    fn widened(&self, bits: u32) -> Self;
+}
+
+#[cfg(feature = "backend-rug")]
+mod rug_backend {
+    use rug::{Complete, Integer};
+
+    use super::BigInt;
+
+    impl BigInt for Integer {
+        fn zero() -> Self {
+            Integer::new()
+        }
+
+        fn one() -> Self {
+            Integer::from(1)
+        }
+
+        fn is_negative(&self) -> bool {
+            self.cmp0().is_lt()
+        }
+
+        fn is_positive(&self) -> bool {
+            self.cmp0().is_gt()
+        }
+
+        fn add(&self, other: &Self) -> Self {
+            (self + other).complete()
+        }
+
+        fn sub(&self, other: &Self) -> Self {
+            (self - other).complete()
+        }
+
+        fn sub_mod(&self, other: &Self, modulus: &Self) -> Self {
+            // `rug`'s subtraction is a true integer difference (can go
+            // negative), so reducing it afterwards is all that's needed
+            BigInt::sub(self, other).modulo(modulus)
+        }
+
+        fn mul(&self, other: &Self) -> Self {
+            (self * other).complete()
+        }
+
+        fn square(&self) -> Self {
+            self.square_ref().complete()
+        }
+
+        fn rem(&self, modulus: &Self) -> Self {
+            (self % modulus).complete()
+        }
+
+        fn modulo(&self, modulus: &Self) -> Self {
+            self.clone().modulo(modulus)
+        }
+
+        fn pow_mod(&self, exponent: &Self, modulus: &Self) -> Option<Self> {
+            Some(self.pow_mod_ref(exponent, modulus)?.into())
+        }
+
+        fn invert(&self, modulus: &Self) -> Option<Self> {
+            Some(self.invert_ref(modulus)?.into())
+        }
+
+        fn gcd(&self, other: &Self) -> Self {
+            self.gcd_ref(other).complete()
+        }
+
+        fn random_below(rng: &mut impl rand_core::RngCore, bound: &Self) -> Self {
+            let mut rng = crate::utils::external_rand(rng);
+            bound.random_below_ref(&mut rng).into()
+        }
+
+        fn precision_bits(&self) -> u32 {
+            self.significant_bits()
+        }
+
+        fn widened(&self, _bits: u32) -> Self {
+            // `rug::Integer` is arbitrary-precision already, so there's
+            // nothing to widen
+            self.clone()
+        }
+    }
+}
+
+/// Pure-Rust backend on top of [`crypto_bigint::BoxedUint`], for builds that
+/// can't link GMP.
+///
+/// `BoxedUint` is unsigned, so [`BigInt::is_negative`] is always `false` and
+/// plain [`BigInt::sub`] wraps around `2^W` (`W` the value's bit precision)
+/// instead of going negative; call sites that need a true modular difference
+/// (e.g. the CRT recombination in [`crate::utils::CrtExp::exp`]) must use
+/// [`BigInt::sub_mod`] instead, which is correct regardless of backend.
+///
+/// `BoxedUint` is also fixed-precision: [`BigInt::mul`]/[`BigInt::square`]
+/// never grow past `max(self, other)`'s existing width, they wrap instead.
+/// Callers that chain multiplications past their operands' starting
+/// precision (again, `CrtExp::build`/`exp`) must [`BigInt::widened`] first;
+/// see that method's docs for why.
+#[cfg(feature = "backend-crypto-bigint")]
+mod crypto_bigint_backend {
+    use crypto_bigint::{
+        modular::{BoxedMontyForm, BoxedMontyParams},
+        BoxedUint, NonZero, RandomMod,
+    };
+
+    use super::BigInt;
+
+    impl BigInt for BoxedUint {
+        fn zero() -> Self {
+            BoxedUint::zero()
+        }
+
+        fn one() -> Self {
+            BoxedUint::one()
+        }
+
+        fn is_negative(&self) -> bool {
+            false
+        }
+
+        fn is_positive(&self) -> bool {
+            !bool::from(self.is_zero())
+        }
+
+        fn add(&self, other: &Self) -> Self {
+            self.wrapping_add(other)
+        }
+
+        fn sub(&self, other: &Self) -> Self {
+            self.wrapping_sub(other)
+        }
+
+        fn sub_mod(&self, other: &Self, modulus: &Self) -> Self {
+            // unlike `sub`, this must never wrap: `self - other` is only a
+            // valid representative of `(self - other) mod modulus` once it's
+            // back in `0..modulus`, which a raw `wrapping_sub` doesn't give
+            if self >= other {
+                self.sub(other)
+            } else {
+                modulus.sub(&other.sub(self))
+            }
+        }
+
+        fn mul(&self, other: &Self) -> Self {
+            self.wrapping_mul(other)
+        }
+
+        fn square(&self) -> Self {
+            self.square()
+        }
+
+        fn rem(&self, modulus: &Self) -> Self {
+            // `modulus` is always one of `CrtExp`/`NaiveExp`'s moduli (`pp`,
+            // `qq`, `nn`), all of which are non-zero by construction
+            #[allow(clippy::expect_used)]
+            let modulus = NonZero::new(modulus.clone()).expect("modulus is non-zero");
+            self.rem_vartime(&modulus)
+        }
+
+        fn modulo(&self, modulus: &Self) -> Self {
+            BigInt::rem(self, modulus)
+        }
+
+        fn pow_mod(&self, exponent: &Self, modulus: &Self) -> Option<Self> {
+            let params = BoxedMontyParams::new_vartime(NonZero::new(modulus.clone())?);
+            let base = BoxedMontyForm::new(self.clone(), params);
+            Some(base.pow(exponent).retrieve())
+        }
+
+        fn invert(&self, modulus: &Self) -> Option<Self> {
+            let modulus = NonZero::new(modulus.clone())?;
+            Option::from(self.inv_mod(&modulus))
+        }
+
+        fn gcd(&self, other: &Self) -> Self {
+            self.gcd(other)
+        }
+
+        fn random_below(rng: &mut impl rand_core::RngCore, bound: &Self) -> Self {
+            // callers (`sample_in_mult_group`) only ever sample below a
+            // modulus, which is non-zero by construction
+            #[allow(clippy::expect_used)]
+            let bound = NonZero::new(bound.clone()).expect("bound is non-zero");
+            BoxedUint::random_mod(rng, &bound)
+        }
+
+        fn precision_bits(&self) -> u32 {
+            self.bits_precision()
+        }
+
+        fn widened(&self, bits: u32) -> Self {
+            self.clone().widen(bits.max(self.bits_precision()))
+        }
+    }
+}